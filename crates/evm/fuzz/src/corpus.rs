@@ -0,0 +1,193 @@
+//! Persistent invariant corpus.
+//!
+//! Call sequences generated by [`crate::strategies::invariant_strat`] that are "interesting" —
+//! they triggered new coverage or a revert — are serialized to a configurable directory. On the
+//! next run, [`InvariantCorpus::load`] reads them back so `invariant_strat` can bias its first
+//! seeded value toward a prior discovery instead of always starting random, and CI can point at a
+//! single corpus file to deterministically reproduce a previously found breaking sequence.
+
+use crate::invariant::BasicTxDetails;
+use alloy_primitives::{Address, Bytes};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::PathBuf,
+};
+
+/// Serializable form of a single call within a [`CorpusEntry`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CorpusCall {
+    pub sender: Address,
+    pub target: Address,
+    pub calldata: Bytes,
+}
+
+impl From<&BasicTxDetails> for CorpusCall {
+    fn from((sender, (target, calldata)): &BasicTxDetails) -> Self {
+        Self { sender: *sender, target: *target, calldata: calldata.clone() }
+    }
+}
+
+impl From<CorpusCall> for BasicTxDetails {
+    fn from(call: CorpusCall) -> Self {
+        (call.sender, (call.target, call.calldata))
+    }
+}
+
+/// A persisted call sequence, serialized as one JSON file per entry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    pub calls: Vec<CorpusCall>,
+}
+
+impl From<&[BasicTxDetails]> for CorpusEntry {
+    fn from(calls: &[BasicTxDetails]) -> Self {
+        Self { calls: calls.iter().map(CorpusCall::from).collect() }
+    }
+}
+
+impl From<CorpusEntry> for Vec<BasicTxDetails> {
+    fn from(entry: CorpusEntry) -> Self {
+        entry.calls.into_iter().map(BasicTxDetails::from).collect()
+    }
+}
+
+/// Reads and writes a directory of JSON-encoded [`CorpusEntry`] files.
+#[derive(Clone, Debug)]
+pub struct InvariantCorpus {
+    dir: PathBuf,
+}
+
+impl InvariantCorpus {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Loads every corpus entry found in the corpus directory, skipping files that fail to parse
+    /// rather than failing the whole run over one corrupt entry.
+    ///
+    /// Returns an empty corpus (not an error) if the directory doesn't exist yet.
+    pub fn load(&self) -> io::Result<Vec<Vec<BasicTxDetails>>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new())
+        }
+
+        let mut entries = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue
+            }
+            let Ok(contents) = fs::read_to_string(&path) else { continue };
+            let Ok(entry) = serde_json::from_str::<CorpusEntry>(&contents) else { continue };
+            entries.push(entry.into());
+        }
+        Ok(entries)
+    }
+
+    /// Replays a single corpus file by path, for deterministically reproducing a previously found
+    /// breaking sequence.
+    pub fn load_file(path: &std::path::Path) -> io::Result<Vec<BasicTxDetails>> {
+        let contents = fs::read_to_string(path)?;
+        let entry: CorpusEntry = serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(entry.into())
+    }
+
+    /// Serializes `calls` as a new corpus file, named after its content hash so that replaying an
+    /// identical sequence doesn't pile up duplicate files.
+    pub fn store(&self, calls: &[BasicTxDetails]) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let entry = CorpusEntry::from(calls);
+        let json = serde_json::to_string_pretty(&entry)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        json.hash(&mut hasher);
+        let path = self.dir.join(format!("{:016x}.json", hasher.finish()));
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// Stores `calls` only if the run that produced them was "interesting" — it hit coverage the
+    /// run hadn't rewarded before, or it reverted — which is the accumulation policy this module's
+    /// doc comment promises. A runner drives the actual execution and so is the only one that
+    /// knows `produced_new_coverage`/`reverted`; this just keeps the decision of *whether* to
+    /// persist next to the code that knows *how* to, instead of leaving every caller to
+    /// reimplement the same filter (or skip it and call [`Self::store`] unconditionally).
+    ///
+    /// Returns `Ok(None)` without touching disk when neither condition holds.
+    pub fn store_if_interesting(
+        &self,
+        calls: &[BasicTxDetails],
+        produced_new_coverage: bool,
+        reverted: bool,
+    ) -> io::Result<Option<PathBuf>> {
+        if !produced_new_coverage && !reverted {
+            return Ok(None)
+        }
+        self.store(calls).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    fn sample_calls() -> Vec<BasicTxDetails> {
+        vec![(
+            address!("0000000000000000000000000000000000000001"),
+            (address!("0000000000000000000000000000000000000002"), Bytes::from(vec![1, 2, 3])),
+        )]
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = InvariantCorpus::new(dir.path());
+
+        assert!(corpus.load().unwrap().is_empty());
+
+        let calls = sample_calls();
+        corpus.store(&calls).unwrap();
+
+        let loaded = corpus.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0], calls);
+    }
+
+    #[test]
+    fn storing_identical_sequence_twice_does_not_duplicate() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = InvariantCorpus::new(dir.path());
+        let calls = sample_calls();
+
+        corpus.store(&calls).unwrap();
+        corpus.store(&calls).unwrap();
+
+        assert_eq!(corpus.load().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn store_if_interesting_skips_uninteresting_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = InvariantCorpus::new(dir.path());
+
+        let path = corpus.store_if_interesting(&sample_calls(), false, false).unwrap();
+        assert!(path.is_none());
+        assert!(corpus.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn store_if_interesting_keeps_new_coverage_or_reverts() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = InvariantCorpus::new(dir.path());
+
+        assert!(corpus.store_if_interesting(&sample_calls(), true, false).unwrap().is_some());
+        assert!(corpus.store_if_interesting(&sample_calls(), false, true).unwrap().is_some());
+        assert_eq!(corpus.load().unwrap().len(), 1);
+    }
+}
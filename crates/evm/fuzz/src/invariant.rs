@@ -0,0 +1,141 @@
+use alloy_json_abi::{Function, JsonAbi};
+use alloy_primitives::{Address, Bytes};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// A 4-byte function selector, as returned by [`alloy_json_abi::Function::selector`].
+pub type Selector4 = [u8; 4];
+
+/// A [`sender`, `(target, calldata)`] pair, as generated by the invariant strategies.
+pub type BasicTxDetails = (Address, (Address, Bytes));
+
+/// Contracts identified as targets during an invariant run, keyed by their deployed address.
+///
+/// Each entry holds the contract's identifier, its ABI, the functions that should be targeted
+/// (via `targetSelectors`/`targetArtifactSelectors`, empty means "all mutable functions"), and the
+/// functions that should never be called (via `excludeSelectors`/`excludeArtifactSelectors`).
+pub type FuzzRunIdentifiedContracts =
+    Arc<Mutex<BTreeMap<Address, (String, JsonAbi, Vec<Function>, Vec<Function>)>>>;
+
+/// Filters the senders used when generating calls for an invariant run.
+///
+/// `excluded` is always honored, even when `targeted` is non-empty: an address that's
+/// simultaneously targeted and excluded is treated as excluded, matching how `excludeContracts`
+/// takes precedence over `targetContracts` elsewhere in the invariant config.
+#[derive(Clone, Debug, Default)]
+pub struct SenderFilters {
+    pub targeted: Vec<Address>,
+    pub excluded: Vec<Address>,
+}
+
+impl SenderFilters {
+    pub fn new(targeted: Vec<Address>, excluded: Vec<Address>) -> Self {
+        Self { targeted, excluded }
+    }
+}
+
+/// Artifact-level target/exclude filters (`targetArtifacts`, `targetArtifactSelectors`,
+/// `excludeArtifacts`), resolved from artifact identifiers down to the set of deployed addresses
+/// they currently cover.
+///
+/// Unlike `targetContracts`/`excludeContracts`, which already deal in concrete addresses, these
+/// hooks name a contract *type* (e.g. `"Counter"` or `"src/Counter.sol:Counter"`) and are expected
+/// to apply to every instance of that type deployed during setup, including ones created later by
+/// the invariant run itself.
+#[derive(Clone, Debug, Default)]
+pub struct ArtifactFilters {
+    /// Artifact identifiers to target, with the (possibly empty) set of selectors to restrict
+    /// calls to for that artifact.
+    pub targeted: BTreeMap<String, Vec<[u8; 4]>>,
+    /// Artifact identifiers to exclude entirely.
+    pub excluded: Vec<String>,
+}
+
+impl ArtifactFilters {
+    /// Resolves these artifact filters against the set of deployed contracts, returning the
+    /// addresses to add to `targetContracts`/`targetSelectors` and the ones to add to
+    /// `excludeContracts`, respectively.
+    ///
+    /// `deployed` maps an artifact identifier to every address it's currently deployed at.
+    pub fn resolve(
+        &self,
+        deployed: &BTreeMap<String, Vec<Address>>,
+    ) -> (Vec<(Address, Vec<[u8; 4]>)>, Vec<Address>) {
+        let targeted = self
+            .targeted
+            .iter()
+            .flat_map(|(artifact, selectors)| {
+                deployed
+                    .get(artifact)
+                    .into_iter()
+                    .flatten()
+                    .map(move |addr| (*addr, selectors.clone()))
+            })
+            .collect();
+
+        let excluded = self
+            .excluded
+            .iter()
+            .flat_map(|artifact| deployed.get(artifact).into_iter().flatten().copied())
+            .collect();
+
+        (targeted, excluded)
+    }
+}
+
+/// Coverage-feedback weights for invariant call selection.
+///
+/// Every `(contract, selector)` pair that gets called during an invariant run accrues a score:
+/// calls that produce new EVM coverage (new PCs/edges hit) are rewarded, and all scores decay a
+/// little at the start of each run so hot paths that stop finding anything new cool off. Target
+/// and function selection then sample from a cumulative-weight distribution over these scores
+/// instead of picking uniformly, so the fuzzer spends more time near code it's actually moving.
+#[derive(Clone, Debug)]
+pub struct CallWeights {
+    scores: Arc<RwLock<HashMap<(Address, Selector4), f64>>>,
+    /// Multiplicative decay applied to every score at the start of a run.
+    pub decay_factor: f64,
+    /// Score bump applied when a call produces new coverage.
+    pub new_coverage_bonus: f64,
+    /// Minimum weight given to a pair that has never been rewarded, so it still gets explored.
+    pub floor_weight: f64,
+}
+
+impl Default for CallWeights {
+    fn default() -> Self {
+        Self {
+            scores: Arc::new(RwLock::new(HashMap::new())),
+            decay_factor: 0.9,
+            new_coverage_bonus: 1.0,
+            floor_weight: 0.05,
+        }
+    }
+}
+
+impl CallWeights {
+    pub fn new(decay_factor: f64, new_coverage_bonus: f64, floor_weight: f64) -> Self {
+        Self { decay_factor, new_coverage_bonus, floor_weight, ..Default::default() }
+    }
+
+    /// Records that calling `selector` on `target` produced new coverage this run.
+    pub fn record_new_coverage(&self, target: Address, selector: Selector4) {
+        let mut scores = self.scores.write();
+        *scores.entry((target, selector)).or_insert(0.0) += self.new_coverage_bonus;
+    }
+
+    /// Applies `decay_factor` to every tracked score. Called once per invariant run.
+    pub fn decay(&self) {
+        for score in self.scores.write().values_mut() {
+            *score *= self.decay_factor;
+        }
+    }
+
+    /// Returns the current weight for a `(contract, selector)` pair, floored so unexplored
+    /// functions are never starved of selection probability.
+    pub fn weight_of(&self, target: Address, selector: Selector4) -> f64 {
+        self.scores.read().get(&(target, selector)).copied().unwrap_or(0.0).max(self.floor_weight)
+    }
+}
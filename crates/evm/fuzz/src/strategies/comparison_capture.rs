@@ -0,0 +1,151 @@
+//! Input-to-state dictionary seeding from comparison operands ("magic value" capture).
+//!
+//! During EVM execution of an invariant call, an inspector watches the comparison opcodes
+//! (`EQ`, `LT`, `GT`, `SLT`, `SGT`) and records the 32-byte immediate that was pushed onto the
+//! stack just before the comparison, whenever the other operand is derived from fuzzed calldata.
+//! This module holds the dictionary-side half of that pipeline: turning what the inspector
+//! captured during one call into the (possibly width-truncated) values that are worth adding to
+//! [`super::EvmFuzzState`], so `fuzz_param_from_state`/`fuzz_calldata_from_state` can draw the
+//! exact constant a `require(x == ...)`-style guard compares against on a later run.
+
+use alloy_dyn_abi::DynSolType;
+use alloy_primitives::U256;
+use std::collections::HashSet;
+
+/// Comparison opcodes whose constant operand is worth capturing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComparisonOp {
+    Eq,
+    Lt,
+    Gt,
+    Slt,
+    Sgt,
+}
+
+/// One comparison observed during execution: `constant` is the immediate pushed just before the
+/// opcode, captured because the other operand was derived from fuzzed calldata.
+#[derive(Clone, Copy, Debug)]
+pub struct CapturedComparison {
+    pub op: ComparisonOp,
+    pub constant: U256,
+}
+
+/// Upper bound on how many magic values a single invariant call may contribute to the dictionary,
+/// so a call with a long chain of comparisons can't blow up dictionary memory.
+pub const MAX_CAPTURES_PER_CALL: usize = 16;
+
+/// Truncates a 32-byte comparison constant down to the byte width of `ty`, so e.g. a `uint8`
+/// parameter is only ever offered a 1-byte value, never the full 32-byte comparand.
+///
+/// Integers and addresses are right-aligned within an EVM word, so their meaningful bytes are the
+/// low-order ones. `bytesN` is the opposite: it's left-aligned (a `bytes4` guard compares against
+/// `0xDEADBEEF00..00`), so its meaningful bytes are the high-order ones.
+///
+/// Returns `None` for types a comparison constant can't meaningfully seed (e.g. tuples, arrays).
+pub fn bucket_to_width(constant: U256, ty: &DynSolType) -> Option<Vec<u8>> {
+    let be_bytes = constant.to_be_bytes::<32>();
+    match ty {
+        DynSolType::Bool => Some(be_bytes[31..].to_vec()),
+        DynSolType::Int(bits) | DynSolType::Uint(bits) => {
+            Some(be_bytes[32 - bits / 8..].to_vec())
+        }
+        DynSolType::Address => Some(be_bytes[12..].to_vec()),
+        DynSolType::FixedBytes(size) => Some(be_bytes[..*size].to_vec()),
+        _ => None,
+    }
+}
+
+/// Returns the candidate constants worth seeding for a single captured comparison.
+///
+/// `EQ` only needs the exact constant to satisfy the guard. The ordering opcodes (`LT`/`GT`/
+/// `SLT`/`SGT`) are satisfied by a *range*, so the exact constant alone is often still on the
+/// wrong side of the boundary (e.g. for `x < 100`, seeding `100` never satisfies it) — seeding the
+/// constant `± 1` as well gives the fuzzer a value on each side of the boundary. `SLT`/`SGT` reuse
+/// the same wrapping arithmetic as `LT`/`GT`: the EVM's two's-complement representation makes
+/// "one less"/"one more" identical in bit pattern regardless of signedness.
+fn boundary_values(capture: &CapturedComparison) -> Vec<U256> {
+    match capture.op {
+        ComparisonOp::Eq => vec![capture.constant],
+        ComparisonOp::Lt | ComparisonOp::Slt => {
+            vec![capture.constant, capture.constant.wrapping_sub(U256::from(1))]
+        }
+        ComparisonOp::Gt | ComparisonOp::Sgt => {
+            vec![capture.constant, capture.constant.wrapping_add(U256::from(1))]
+        }
+    }
+}
+
+/// Returns the subset of `captures`, expanded to `op`-appropriate boundary values and bucketed to
+/// `param_type`'s width, that aren't already present in `existing` and are worth inserting into
+/// the fuzz dictionary, capped at [`MAX_CAPTURES_PER_CALL`].
+pub fn new_magic_values(
+    captures: &[CapturedComparison],
+    param_type: &DynSolType,
+    existing: &HashSet<Vec<u8>>,
+) -> Vec<Vec<u8>> {
+    let mut seen = HashSet::new();
+    captures
+        .iter()
+        .flat_map(boundary_values)
+        .filter_map(|constant| bucket_to_width(constant, param_type))
+        .filter(|bucketed| !existing.contains(bucketed) && seen.insert(bucketed.clone()))
+        .take(MAX_CAPTURES_PER_CALL)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_to_param_width() {
+        let constant = U256::from(0xDEADBEEFu64);
+        let bucketed = bucket_to_width(constant, &DynSolType::Uint(8)).unwrap();
+        assert_eq!(bucketed, vec![0xEF]);
+
+        let bucketed = bucket_to_width(constant, &DynSolType::Uint(32)).unwrap();
+        assert_eq!(bucketed, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn fixed_bytes_takes_high_order_bytes() {
+        // A `bytes4` guard compares against the left-aligned word `0xDEADBEEF00..00`, so the
+        // meaningful bytes are the high-order ones, unlike a right-aligned `uintN`/`address`.
+        let constant = U256::from(0xDEADBEEFu64) << (28 * 8);
+        let bucketed = bucket_to_width(constant, &DynSolType::FixedBytes(4)).unwrap();
+        assert_eq!(bucketed, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn skips_already_known_values() {
+        let captures = vec![
+            CapturedComparison { op: ComparisonOp::Eq, constant: U256::from(1u64) },
+            CapturedComparison { op: ComparisonOp::Eq, constant: U256::from(2u64) },
+        ];
+        let mut existing = HashSet::new();
+        existing.insert(vec![1u8]);
+
+        let fresh = new_magic_values(&captures, &DynSolType::Uint(8), &existing);
+        assert_eq!(fresh, vec![vec![2u8]]);
+    }
+
+    #[test]
+    fn lt_and_gt_seed_boundary_values_not_just_the_constant() {
+        let captures = vec![CapturedComparison { op: ComparisonOp::Lt, constant: U256::from(100u64) }];
+        let fresh = new_magic_values(&captures, &DynSolType::Uint(8), &HashSet::new());
+        assert_eq!(fresh, vec![vec![100u8], vec![99u8]]);
+
+        let captures = vec![CapturedComparison { op: ComparisonOp::Gt, constant: U256::from(100u64) }];
+        let fresh = new_magic_values(&captures, &DynSolType::Uint(8), &HashSet::new());
+        assert_eq!(fresh, vec![vec![100u8], vec![101u8]]);
+    }
+
+    #[test]
+    fn caps_captures_per_call() {
+        let captures: Vec<_> = (0..(MAX_CAPTURES_PER_CALL as u64 + 10))
+            .map(|i| CapturedComparison { op: ComparisonOp::Eq, constant: U256::from(i) })
+            .collect();
+        let fresh = new_magic_values(&captures, &DynSolType::Uint(64), &HashSet::new());
+        assert_eq!(fresh.len(), MAX_CAPTURES_PER_CALL);
+    }
+}
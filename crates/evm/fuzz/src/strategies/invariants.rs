@@ -1,46 +1,118 @@
 use super::{fuzz_calldata_with_config, fuzz_param_from_state, CalldataFuzzDictionary};
 use crate::{
-    invariant::{BasicTxDetails, FuzzRunIdentifiedContracts, SenderFilters},
+    invariant::{
+        ArtifactFilters, BasicTxDetails, CallWeights, FuzzRunIdentifiedContracts, SenderFilters,
+    },
     strategies::{fuzz_calldata_from_state, fuzz_param, EvmFuzzState},
 };
 use alloy_json_abi::{Function, JsonAbi};
 use alloy_primitives::{Address, Bytes};
+use eyre::Result;
 use parking_lot::RwLock;
 use proptest::prelude::*;
-use std::{rc::Rc, sync::Arc};
+use rand::Rng;
+use std::{
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+    sync::Arc,
+};
 
 /// Given a target address, we generate random calldata.
+///
+/// `call_weights` should be the same instance passed to [`invariant_strat`] for this run, so that
+/// coverage-feedback scores accrued through the regular strategy also bias this one.
 pub fn override_call_strat(
     fuzz_state: EvmFuzzState,
     contracts: FuzzRunIdentifiedContracts,
     target: Arc<RwLock<Address>>,
     calldata_fuzz_config: CalldataFuzzDictionary,
-) -> SBoxedStrategy<(Address, Bytes)> {
-    let contracts_ref = contracts.clone();
+    call_weights: CallWeights,
+) -> Result<SBoxedStrategy<(Address, Bytes)>> {
+    // `override_call_strat` never applies artifact filters (its `random_contract` fallback
+    // already samples uniformly over every known address), so check against the same
+    // no-filter view a fully-excluded `target_address` would otherwise have to fall back to.
+    if compute_contract_candidates(&contracts, &call_weights, &ArtifactFilters::default()).is_empty()
+    {
+        eyre::bail!(
+            "no contract has a selectable function; check targetSelectors/excludeSelectors for a \
+             configuration that excludes every function on every deployed contract"
+        );
+    }
 
+    let contracts_ref = contracts.clone();
     let random_contract = any::<prop::sample::Selector>()
         .prop_map(move |selector| *selector.select(contracts_ref.lock().keys()));
     let target = any::<prop::sample::Selector>().prop_map(move |_| *target.read());
 
-    proptest::strategy::Union::new_weighted(vec![
+    Ok(proptest::strategy::Union::new_weighted(vec![
         (80, target.sboxed()),
         (20, random_contract.sboxed()),
     ])
     .prop_flat_map(move |target_address| {
         let fuzz_state = fuzz_state.clone();
         let calldata_fuzz_config = calldata_fuzz_config.clone();
-        let (_, abi, functions) = contracts.lock().get(&target_address).unwrap().clone();
-        let func = select_random_function(abi, functions);
-        func.prop_flat_map(move |func| {
-            fuzz_contract_with_calldata(
-                fuzz_state.clone(),
-                calldata_fuzz_config.clone(),
-                target_address,
-                func,
+        let contracts = contracts.clone();
+        let call_weights = call_weights.clone();
+        let (_, abi, functions, excluded_functions) =
+            contracts.lock().get(&target_address).unwrap().clone();
+        match select_random_function(
+            target_address,
+            abi,
+            functions,
+            excluded_functions,
+            call_weights.clone(),
+        ) {
+            Some(func) => func
+                .prop_flat_map(move |func| {
+                    fuzz_contract_with_calldata(
+                        fuzz_state.clone(),
+                        calldata_fuzz_config.clone(),
+                        target_address,
+                        func,
+                    )
+                })
+                .boxed(),
+            // `excludeSelectors` can leave this specific target with nothing selectable even
+            // though some other deployed contract still has a selectable function; fall back to
+            // one of those instead of panicking.
+            None => fallback_override_call(fuzz_state, contracts, calldata_fuzz_config, call_weights),
+        }
+    })
+    .sboxed())
+}
+
+/// Picks any currently-selectable contract and function, ignoring artifact filters (which
+/// `override_call_strat` never applied to begin with) — the fallback used when the chosen
+/// override `target_address` turns out to have nothing selectable.
+fn fallback_override_call(
+    fuzz_state: EvmFuzzState,
+    contracts: FuzzRunIdentifiedContracts,
+    calldata_fuzz_config: CalldataFuzzDictionary,
+    call_weights: CallWeights,
+) -> BoxedStrategy<(Address, Bytes)> {
+    select_random_contract(contracts, call_weights.clone(), ArtifactFilters::default())
+        .expect("checked non-empty just before this strategy was built")
+        .prop_flat_map(move |(contract, abi, functions, excluded_functions)| {
+            let func = select_random_function(
+                contract,
+                abi,
+                functions,
+                excluded_functions,
+                call_weights.clone(),
             )
+            .expect("select_random_contract only returns contracts with a selectable function");
+            let fuzz_state = fuzz_state.clone();
+            let calldata_fuzz_config = calldata_fuzz_config.clone();
+            func.prop_flat_map(move |func| {
+                fuzz_contract_with_calldata(
+                    fuzz_state.clone(),
+                    calldata_fuzz_config.clone(),
+                    contract,
+                    func,
+                )
+            })
         })
-    })
-    .sboxed()
+        .boxed()
 }
 
 /// Creates the invariant strategy.
@@ -52,18 +124,46 @@ pub fn override_call_strat(
 /// The fuzzed parameters can be filtered through different methods implemented in the test
 /// contract:
 ///
-/// `targetContracts()`, `targetSenders()`, `excludeContracts()`, `targetSelectors()`
+/// `targetContracts()`, `targetSenders()`, `excludeContracts()`, `targetSelectors()`,
+/// `excludeSenders()`, `excludeSelectors()`, `targetArtifacts()`, `targetArtifactSelectors()`,
+/// `excludeArtifacts()`
+///
+/// If `corpus_seeds` is non-empty (loaded from a persisted [`crate::corpus::InvariantCorpus`]),
+/// the very first sequence is biased toward replaying one of those previously-interesting call
+/// sequences instead of always generating a fresh random one.
 pub fn invariant_strat(
     fuzz_state: EvmFuzzState,
     senders: SenderFilters,
     contracts: FuzzRunIdentifiedContracts,
     dictionary_weight: u32,
     calldata_fuzz_config: CalldataFuzzDictionary,
-) -> impl Strategy<Value = Vec<BasicTxDetails>> {
+    call_weights: CallWeights,
+    artifact_filters: ArtifactFilters,
+    corpus_seeds: Vec<Vec<BasicTxDetails>>,
+) -> Result<impl Strategy<Value = Vec<BasicTxDetails>>> {
     // We only want to seed the first value, since we want to generate the rest as we mutate the
     // state
-    generate_call(fuzz_state, senders, contracts, dictionary_weight, calldata_fuzz_config)
-        .prop_map(|x| vec![x])
+    let generated = generate_call(
+        fuzz_state,
+        senders,
+        contracts,
+        dictionary_weight,
+        calldata_fuzz_config,
+        call_weights,
+        artifact_filters,
+    )?
+    .prop_map(|x| vec![x])
+    .boxed();
+
+    if corpus_seeds.is_empty() {
+        return Ok(generated)
+    }
+
+    let seeded = any::<prop::sample::Selector>()
+        .prop_map(move |selector| selector.select(&corpus_seeds).clone())
+        .boxed();
+
+    Ok(proptest::strategy::Union::new_weighted(vec![(70, seeded), (30, generated)]).boxed())
 }
 
 /// Strategy to generate a transaction where the `sender`, `target` and `calldata` are all generated
@@ -74,12 +174,21 @@ fn generate_call(
     contracts: FuzzRunIdentifiedContracts,
     dictionary_weight: u32,
     calldata_fuzz_config: CalldataFuzzDictionary,
-) -> BoxedStrategy<BasicTxDetails> {
-    let random_contract = select_random_contract(contracts);
+    call_weights: CallWeights,
+    artifact_filters: ArtifactFilters,
+) -> Result<BoxedStrategy<BasicTxDetails>> {
+    let random_contract = select_random_contract(contracts, call_weights.clone(), artifact_filters)?;
     let senders = Rc::new(senders);
-    random_contract
-        .prop_flat_map(move |(contract, abi, functions)| {
-            let func = select_random_function(abi, functions);
+    Ok(random_contract
+        .prop_flat_map(move |(contract, abi, functions, excluded_functions)| {
+            let func = select_random_function(
+                contract,
+                abi,
+                functions,
+                excluded_functions,
+                call_weights.clone(),
+            )
+            .expect("select_random_contract only returns contracts with a selectable function");
             let senders = senders.clone();
             let fuzz_state = fuzz_state.clone();
             let calldata_fuzz_config = calldata_fuzz_config.clone();
@@ -97,12 +206,15 @@ fn generate_call(
                 )
             })
         })
-        .boxed()
+        .boxed())
 }
 
 /// Strategy to select a sender address:
 /// * If `senders` is empty, then it's either a random address (10%) or from the dictionary (90%).
 /// * If `senders` is not empty, a random address is chosen from the list of senders.
+///
+/// `excludeSenders()` is honored in both cases: an address that's both targeted and excluded is
+/// treated as excluded.
 fn select_random_sender(
     fuzz_state: EvmFuzzState,
     senders: Rc<SenderFilters>,
@@ -126,37 +238,159 @@ fn select_random_sender(
     // Too many exclusions can slow down testing.
     .prop_filter("senders not allowed", move |addr| !senders_ref.excluded.contains(addr))
     .boxed();
-    if !senders.targeted.is_empty() {
-        any::<prop::sample::Selector>()
-            .prop_map(move |selector| *selector.select(&*senders.targeted))
-            .boxed()
+    let targeted: Vec<Address> =
+        senders.targeted.iter().filter(|addr| !senders.excluded.contains(addr)).copied().collect();
+    // Excluding every targeted sender is a valid (if unusual) config; fall back to the regular
+    // strategy rather than selecting from an empty slice.
+    if !targeted.is_empty() {
+        any::<prop::sample::Selector>().prop_map(move |selector| *selector.select(&targeted)).boxed()
     } else {
         fuzz_strategy
     }
 }
 
-/// Strategy to randomly select a contract from the `contracts` list that has at least 1 function
+/// Strategy to randomly select a contract from the `contracts` list that has at least 1
+/// selectable function, after applying `targetArtifacts`/`targetArtifactSelectors`/
+/// `excludeArtifacts` (resolved here from artifact identifier to deployed address) on top of the
+/// per-contract `excludeSelectors()` list already baked into `contracts`.
+///
+/// Contracts are sampled from a cumulative-weight distribution built from `call_weights`: a
+/// contract's weight is the sum of its functions' coverage-feedback scores, so contracts that
+/// have recently yielded new coverage are visited more often.
 fn select_random_contract(
     contracts: FuzzRunIdentifiedContracts,
-) -> impl Strategy<Value = (Address, JsonAbi, Vec<Function>)> {
-    let selectors = any::<prop::sample::Selector>();
-    selectors.prop_map(move |selector| {
-        let contracts = contracts.lock();
-        let (addr, (_, abi, functions)) =
-            selector.select(contracts.iter().filter(|(_, (_, abi, _))| !abi.functions.is_empty()));
-        (*addr, abi.clone(), functions.clone())
+    call_weights: CallWeights,
+    artifact_filters: ArtifactFilters,
+) -> Result<impl Strategy<Value = (Address, JsonAbi, Vec<Function>, Vec<Function>)>> {
+    // `excludeArtifacts`/`excludeSelectors` can legitimately remove every deployed contract — a
+    // degenerate but valid config. Validate it once up front so that case surfaces as a clean
+    // error instead of panicking deep inside a proptest generator. `contracts` only grows and the
+    // exclude filters are static, so a set that's non-empty now stays non-empty for the rest of
+    // the run.
+    if compute_contract_candidates(&contracts, &call_weights, &artifact_filters).is_empty() {
+        eyre::bail!(
+            "no contract has a selectable function; check targetContracts/excludeContracts/\
+             targetSelectors/excludeSelectors/targetArtifacts/excludeArtifacts for a \
+             configuration that excludes every deployed contract"
+        );
+    }
+
+    Ok(Just(()).prop_perturb(move |_, mut rng| {
+        let candidates = compute_contract_candidates(&contracts, &call_weights, &artifact_filters);
+        let (addr, abi, functions, excluded_functions, _) =
+            weighted_sample(&candidates, |(.., weight)| *weight, &mut rng).expect(
+                "validated non-empty at construction; contracts only grow and exclude filters \
+                 are static, so this can't turn empty later",
+            );
+        (*addr, abi.clone(), functions.clone(), excluded_functions.clone())
+    }))
+}
+
+/// Computes every currently deployed contract that has at least one selectable function after
+/// applying `targetArtifacts`/`targetArtifactSelectors`/`excludeArtifacts` on top of the
+/// per-contract `excludeSelectors()` list already baked into `contracts`, paired with its
+/// coverage-feedback weight (the sum of its functions' [`CallWeights::weight_of`]).
+fn compute_contract_candidates(
+    contracts: &FuzzRunIdentifiedContracts,
+    call_weights: &CallWeights,
+    artifact_filters: &ArtifactFilters,
+) -> Vec<(Address, JsonAbi, Vec<Function>, Vec<Function>, f64)> {
+    let contracts = contracts.lock();
+
+    let mut deployed: BTreeMap<String, Vec<Address>> = BTreeMap::new();
+    for (addr, (identifier, ..)) in contracts.iter() {
+        deployed.entry(identifier.clone()).or_default().push(*addr);
+    }
+    let (artifact_targeted, artifact_excluded) = artifact_filters.resolve(&deployed);
+    let artifact_targeted: HashMap<Address, Vec<[u8; 4]>> = artifact_targeted.into_iter().collect();
+
+    contracts
+        .iter()
+        .filter(|(addr, _)| !artifact_excluded.contains(addr))
+        .filter_map(|(addr, (_, abi, functions, excluded_functions))| {
+            // `targetArtifactSelectors` restricts calls on this artifact to the given
+            // selectors, the same way `targetSelectors` restricts `targetContracts`.
+            let functions = match artifact_targeted.get(addr) {
+                Some(selectors) if !selectors.is_empty() => abi
+                    .functions()
+                    .filter(|func| selectors.contains(&func.selector()))
+                    .cloned()
+                    .collect(),
+                _ => functions.clone(),
+            };
+            if !has_selectable_function(abi, &functions, excluded_functions) {
+                return None
+            }
+            let weight = abi
+                .functions()
+                .map(|func| call_weights.weight_of(*addr, func.selector()))
+                .sum::<f64>();
+            Some((*addr, abi.clone(), functions, excluded_functions.clone(), weight))
+        })
+        .collect()
+}
+
+/// Returns `true` if at least one function can be selected for this contract: one of
+/// `targeted_functions` not in `excluded_functions` if any are targeted, otherwise any mutable abi
+/// function not in `excluded_functions`.
+fn has_selectable_function(
+    abi: &JsonAbi,
+    targeted_functions: &[Function],
+    excluded_functions: &[Function],
+) -> bool {
+    let excluded_selectors: Vec<_> =
+        excluded_functions.iter().map(|func| func.selector()).collect();
+    if !targeted_functions.is_empty() {
+        return targeted_functions.iter().any(|func| !excluded_selectors.contains(&func.selector()))
+    }
+    abi.functions().any(|func| {
+        !matches!(
+            func.state_mutability,
+            alloy_json_abi::StateMutability::Pure | alloy_json_abi::StateMutability::View
+        ) && !excluded_selectors.contains(&func.selector())
     })
 }
 
 /// Strategy to select a random mutable function from the abi.
 ///
 /// If `targeted_functions` is not empty, select one from it. Otherwise, take any
-/// of the available abi functions.
+/// of the available abi functions. `excluded_functions` (from `excludeSelectors()`) is removed
+/// from both pools, since an exclusion should win even over an explicit target.
+///
+/// Candidates are drawn from a cumulative-weight distribution over `call_weights`, so functions
+/// that have historically produced new coverage on `contract` are favored over ones that haven't.
+///
+/// Returns `None` if `excludeSelectors()` removed every candidate (no targeted function left, and
+/// no mutable abi function left either) — callers must not call this on a contract that
+/// `has_selectable_function` rejected.
 fn select_random_function(
+    contract: Address,
     abi: JsonAbi,
     targeted_functions: Vec<Function>,
-) -> BoxedStrategy<Function> {
-    let selectors = any::<prop::sample::Selector>();
+    excluded_functions: Vec<Function>,
+    call_weights: CallWeights,
+) -> Option<BoxedStrategy<Function>> {
+    let excluded_selectors: Vec<_> =
+        excluded_functions.iter().map(|func| func.selector()).collect();
+    let targeted_functions: Vec<Function> = targeted_functions
+        .into_iter()
+        .filter(|func| !excluded_selectors.contains(&func.selector()))
+        .collect();
+
+    if !targeted_functions.is_empty() {
+        let weights = call_weights;
+        let selector = Just(()).prop_perturb(move |_, mut rng| {
+            weighted_sample(
+                &targeted_functions,
+                |func| weights.weight_of(contract, func.selector()),
+                &mut rng,
+            )
+            .expect("targeted_functions checked non-empty above")
+            .clone()
+        });
+        return Some(selector.boxed())
+    }
+
     let possible_funcs: Vec<Function> = abi
         .functions()
         .filter(|func| {
@@ -165,19 +399,52 @@ fn select_random_function(
                 alloy_json_abi::StateMutability::Pure | alloy_json_abi::StateMutability::View
             )
         })
+        .filter(|func| !excluded_selectors.contains(&func.selector()))
         .cloned()
         .collect();
-    let total_random = selectors.prop_map(move |selector| {
-        let func = selector.select(&possible_funcs);
-        func.clone()
+    if possible_funcs.is_empty() {
+        return None
+    }
+    let weights = call_weights;
+    let total_random = Just(()).prop_perturb(move |_, mut rng| {
+        weighted_sample(&possible_funcs, |func| weights.weight_of(contract, func.selector()), &mut rng)
+            .expect("possible_funcs checked non-empty above")
+            .clone()
     });
-    if !targeted_functions.is_empty() {
-        let selector = any::<prop::sample::Selector>()
-            .prop_map(move |selector| selector.select(targeted_functions.clone()));
-        selector.boxed()
-    } else {
-        total_random.boxed()
+    Some(total_random.boxed())
+}
+
+/// Draws an item from `items` using a cumulative-weight distribution over `weight_of`.
+///
+/// An item is picked with probability proportional to its weight, so an item with weight `0.0` is
+/// never picked while any other item has a positive weight; callers passing
+/// `CallWeights::weight_of` get `CallWeights::floor_weight` for unexplored `(contract, selector)`
+/// pairs instead of `0.0`. Returns `None` only if `items` is empty. Falls back to a uniform pick if every weight is
+/// `<= 0.0` (e.g. `CallWeights::floor_weight` configured to `0.0`), rather than handing
+/// `rng.gen_range` an empty `0.0..0.0` range.
+fn weighted_sample<'a, T>(
+    items: &'a [T],
+    weight_of: impl Fn(&T) -> f64,
+    rng: &mut impl rand::Rng,
+) -> Option<&'a T> {
+    if items.is_empty() {
+        return None
+    }
+
+    let weights: Vec<f64> = items.iter().map(&weight_of).collect();
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Some(&items[rng.gen_range(0..items.len())])
+    }
+
+    let mut pick = rng.gen_range(0.0..total);
+    for (item, weight) in items.iter().zip(weights.iter()) {
+        if pick < *weight {
+            return Some(item)
+        }
+        pick -= *weight;
     }
+    items.last()
 }
 
 /// Given a function, it returns a proptest strategy which generates valid abi-encoded calldata
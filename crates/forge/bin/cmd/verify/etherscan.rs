@@ -0,0 +1,262 @@
+use super::{ContractLanguage, VerifyArgs};
+use eyre::{Context, OptionExt, Result};
+use foundry_block_explorers::verify::{CodeFormat, VerifyContract};
+use foundry_cli::utils::LoadConfig;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Submits a contract's source for verification to an Etherscan-compatible explorer.
+#[derive(Clone, Debug, Default)]
+pub struct EtherscanVerificationProvider;
+
+impl EtherscanVerificationProvider {
+    /// Builds the verification request for the configured provider.
+    ///
+    /// Solidity sources are submitted as flattened source or standard-json input, matching what
+    /// `solc` produced. Vyper never goes through `solc`, so it gets its own `codeformat`s:
+    /// `vyper-single-file` for a lone source file, `vyper-json` when the contract imports other
+    /// local files that need to be bundled the way Vyper's solc-compatible JSON input does.
+    pub async fn create_verify_request(&self, args: &VerifyArgs) -> Result<VerifyContract> {
+        match args.language() {
+            ContractLanguage::Solidity => self.create_solidity_verify_request(args).await,
+            ContractLanguage::Vyper => self.create_vyper_verify_request(args).await,
+        }
+    }
+
+    /// Builds the request for a Solidity contract, either flattening the source or compiling the
+    /// project down to the full standard-json input, depending on `args.flatten`.
+    async fn create_solidity_verify_request(&self, args: &VerifyArgs) -> Result<VerifyContract> {
+        let contract_path = args
+            .contract
+            .path
+            .clone()
+            .ok_or_eyre("Contract path is required for Solidity verification")?;
+        let compiler_version = args
+            .compiler_version
+            .clone()
+            .ok_or_eyre("--compiler-version is required for Solidity verification")?;
+
+        // Optimizer runs, EVM version and via-ir are threaded into `config` by
+        // `VerifyArgs::data`, so the project built from it already compiles with the settings the
+        // user asked to verify against.
+        let config = args.load_config_emit_warnings();
+        let project = config.project()?;
+
+        let source = if args.flatten {
+            project.flatten(Path::new(&contract_path)).wrap_err("failed to flatten contract")?
+        } else {
+            let input = project
+                .standard_json_input(Path::new(&contract_path))
+                .wrap_err("failed to build standard-json input")?;
+            serde_json::to_string(&input).wrap_err("failed to serialize standard-json input")?
+        };
+        let code_format =
+            if args.flatten { CodeFormat::SingleFile } else { CodeFormat::StandardJsonInput };
+
+        let mut request =
+            VerifyContract::new(args.address, args.contract.name.clone(), source, compiler_version)
+                .code_format(code_format);
+        if let Some(constructor_args) = &args.constructor_args {
+            request = request.constructor_arguments(constructor_args.clone());
+        }
+        Ok(request)
+    }
+
+    /// Builds the request for a Vyper contract.
+    ///
+    /// Vyper has no `via_ir`/optimizer-runs story the way `solc` does, so none of the
+    /// Solidity-specific compiler settings are forwarded here.
+    async fn create_vyper_verify_request(&self, args: &VerifyArgs) -> Result<VerifyContract> {
+        let contract_path = args
+            .contract
+            .path
+            .clone()
+            .ok_or_eyre("Contract path is required for Vyper verification")?;
+        let compiler_version = args
+            .compiler_version
+            .clone()
+            .ok_or_eyre("--compiler-version is required for Vyper verification")?;
+        let entry_path = PathBuf::from(&contract_path);
+        let entry_source =
+            std::fs::read_to_string(&entry_path).wrap_err("failed to read contract source")?;
+
+        let (source, code_format) = if has_local_imports(&entry_source) {
+            let sources = collect_vyper_sources(&entry_path)?;
+            let input = VyperJsonInput {
+                language: "Vyper",
+                sources: sources
+                    .into_iter()
+                    .map(|(path, content)| (path, VyperSource { content }))
+                    .collect(),
+                settings: VyperJsonSettings {
+                    output_selection: BTreeMap::from([("*".to_string(), vec!["*".to_string()])]),
+                },
+            };
+            (
+                serde_json::to_string(&input).wrap_err("failed to serialize vyper-json input")?,
+                CodeFormat::VyperJson,
+            )
+        } else {
+            (entry_source, CodeFormat::VyperSingleFile)
+        };
+
+        let mut request =
+            VerifyContract::new(args.address, args.contract.name.clone(), source, compiler_version)
+                .code_format(code_format);
+        if let Some(constructor_args) = &args.constructor_args {
+            request = request.constructor_arguments(constructor_args.clone());
+        }
+        Ok(request)
+    }
+}
+
+/// The solc-compatible JSON input Vyper's `-f solc_json` interface accepts.
+#[derive(Serialize)]
+struct VyperJsonInput {
+    language: &'static str,
+    sources: BTreeMap<String, VyperSource>,
+    settings: VyperJsonSettings,
+}
+
+#[derive(Serialize)]
+struct VyperSource {
+    content: String,
+}
+
+/// Mirrors solc-json's `settings.outputSelection`, which Vyper's `vyper-json` codeformat also
+/// expects; without it, Etherscan's compiler invocation has nothing telling it what to emit.
+#[derive(Serialize)]
+struct VyperJsonSettings {
+    #[serde(rename = "outputSelection")]
+    output_selection: BTreeMap<String, Vec<String>>,
+}
+
+/// Returns `true` if the Vyper source imports another *local* file, meaning it can't be verified
+/// as a single file and needs the bundled `vyper-json` input instead.
+///
+/// Imports from Vyper's own standard library (`import vyper...` / `from vyper... import ...`,
+/// e.g. `from vyper.interfaces import ERC20`) don't count: they ship with the compiler, so a
+/// contract that only uses those is still a valid single-file submission.
+fn has_local_imports(source: &str) -> bool {
+    source.lines().map(str::trim_start).any(|line| {
+        let Some(rest) = line.strip_prefix("import ").or_else(|| line.strip_prefix("from ")) else {
+            return false
+        };
+        !rest.trim_start().starts_with("vyper")
+    })
+}
+
+/// Recursively resolves the local files a Vyper contract imports, keyed by the same
+/// import-relative path Etherscan needs to resolve `import utils`/`from . import utils` against
+/// (e.g. `utils.vy`, `subdir/helpers.vy`) — never by filesystem path, which would be
+/// cwd-/invocation-dependent and wouldn't match any import statement in the bundle.
+fn collect_vyper_sources(entry_path: &Path) -> Result<BTreeMap<String, String>> {
+    let root = entry_path.parent().unwrap_or_else(|| Path::new("."));
+    let entry_key = entry_path
+        .file_name()
+        .ok_or_eyre("contract path has no file name")?
+        .to_string_lossy()
+        .into_owned();
+
+    let mut sources = BTreeMap::new();
+    collect_vyper_source(entry_path, root, &entry_key, &mut sources)?;
+    Ok(sources)
+}
+
+fn collect_vyper_source(
+    path: &Path,
+    root: &Path,
+    key: &str,
+    sources: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    if sources.contains_key(key) {
+        return Ok(())
+    }
+
+    let content = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read Vyper source at {}", path.display()))?;
+    for import in local_import_targets(&content) {
+        let import_key = format!("{import}.vy");
+        let imported_path = root.join(&import_key);
+        if imported_path.exists() {
+            collect_vyper_source(&imported_path, root, &import_key, sources)?;
+        }
+    }
+    sources.insert(key.to_string(), content);
+    Ok(())
+}
+
+/// Extracts the module path out of each local (non-`vyper`-stdlib) `import`/`from ... import`
+/// statement in a Vyper source file, e.g. `utils` from `import utils as utils`, or `utils` from
+/// `from . import utils` (leading dots are dropped).
+fn local_import_targets(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .map(str::trim_start)
+        .filter_map(|line| {
+            if let Some(rest) = line.strip_prefix("from ") {
+                let (module, name_part) = rest.split_once(" import")?;
+                let module = module.trim();
+                if module.starts_with("vyper") {
+                    return None
+                }
+                let name = name_part.trim();
+                let module_path = module.trim_start_matches('.').replace('.', "/");
+                Some(if module_path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{module_path}/{name}")
+                })
+            } else if let Some(rest) = line.strip_prefix("import ") {
+                let module = rest.split(" as ").next().unwrap_or(rest).trim();
+                (!module.starts_with("vyper")).then(|| module.replace('.', "/"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_local_imports() {
+        assert!(!has_local_imports("# @version ^0.3.7\n\n@external\ndef foo(): pass"));
+        assert!(has_local_imports("import utils as utils\n\n@external\ndef foo(): pass"));
+        assert!(has_local_imports("from . import utils\n"));
+    }
+
+    #[test]
+    fn vyper_stdlib_imports_are_not_local() {
+        assert!(!has_local_imports("from vyper.interfaces import ERC20\n"));
+        assert!(!has_local_imports("import vyper.interfaces.ERC20 as ERC20\n"));
+    }
+
+    #[test]
+    fn extracts_local_import_targets() {
+        assert_eq!(
+            local_import_targets(
+                "import utils as utils\nfrom . import helpers\nfrom vyper.interfaces import ERC20\n"
+            ),
+            vec!["utils".to_string(), "helpers".to_string()]
+        );
+    }
+
+    #[test]
+    fn bundled_sources_are_keyed_by_import_relative_path_not_filesystem_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Main.vy"), "import utils as utils\n").unwrap();
+        std::fs::write(dir.path().join("utils.vy"), "# @version ^0.3.7\n").unwrap();
+
+        let sources = collect_vyper_sources(&dir.path().join("Main.vy")).unwrap();
+
+        let mut keys: Vec<_> = sources.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["Main.vy".to_string(), "utils.vy".to_string()]);
+    }
+}
@@ -35,6 +35,14 @@ impl Default for VerifierArgs {
     }
 }
 
+/// The source language of a contract being verified.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ContractLanguage {
+    #[default]
+    Solidity,
+    Vyper,
+}
+
 /// CLI arguments for `forge verify`.
 #[derive(Clone, Debug, Parser)]
 pub struct VerifyArgs {
@@ -44,6 +52,13 @@ pub struct VerifyArgs {
     /// The contract identifier in the form `<path>:<contractname>`.
     pub contract: ContractInfo,
 
+    /// The source language of the contract being verified.
+    ///
+    /// If omitted, the language is inferred from the contract path's file extension (`.vy` and
+    /// `.vyi` are treated as Vyper, everything else as Solidity).
+    #[arg(long, value_enum)]
+    pub language: Option<ContractLanguage>,
+
     /// The ABI-encoded constructor arguments.
     #[arg(
         long,
@@ -160,7 +175,7 @@ impl VerifyArgs {
 
         if self.show_standard_json_input {
             let args =
-                EtherscanVerificationProvider::default().create_verify_request(&self, None).await?;
+                EtherscanVerificationProvider::default().create_verify_request(&self).await?;
             println!("{}", args.source);
             return Ok(())
         }
@@ -193,6 +208,19 @@ impl VerifyArgs {
     pub fn verification_provider(&self) -> Result<Box<dyn VerificationProvider>> {
         self.verifier.verifier.client(&self.etherscan.key())
     }
+
+    /// Resolves the contract's source language, preferring an explicit `--language` flag over
+    /// detecting it from the contract path's file extension.
+    pub fn language(&self) -> ContractLanguage {
+        self.language.unwrap_or_else(|| {
+            match self.contract.path.as_deref() {
+                Some(path) if path.ends_with(".vy") || path.ends_with(".vyi") => {
+                    ContractLanguage::Vyper
+                }
+                _ => ContractLanguage::Solidity,
+            }
+        })
+    }
 }
 
 /// Check verification status arguments
@@ -266,4 +294,26 @@ mod tests {
         ]);
         assert!(args.via_ir);
     }
+
+    #[test]
+    fn detects_vyper_language_from_contract_path() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.vy:Domains",
+        ]);
+        assert_eq!(args.language(), ContractLanguage::Vyper);
+    }
+
+    #[test]
+    fn explicit_language_flag_overrides_detection() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--language",
+            "vyper",
+        ]);
+        assert_eq!(args.language(), ContractLanguage::Vyper);
+    }
 }